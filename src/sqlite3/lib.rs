@@ -1,18 +1,34 @@
-use std::libc::c_int;
+use std::libc::{c_int, c_void};
+use std::cast;
 use std::ptr;
 use std::str;
+use std::vec;
 
 mod ffi {
-    use std::libc::{c_char, c_int, c_void};
+    use std::libc::{c_char, c_int, c_void, c_double};
     use std::cast;
 
     pub type sqlite3 = c_void;
     pub type sqlite3_stmt = c_void;
+    pub type sqlite3_backup = c_void;
+    pub type sqlite3_context = c_void;
+    pub type sqlite3_value = c_void;
+
+    pub static SQLITE_UTF8: c_int = 1;
 
     pub static SQLITE_OK: c_int = 0;
+    pub static SQLITE_BUSY: c_int = 5;
+    pub static SQLITE_LOCKED: c_int = 6;
     pub static SQLITE_ROW: c_int = 100;
     pub static SQLITE_DONE: c_int = 101;
 
+    // Column/value type tags, as returned by sqlite3_column_type.
+    pub static SQLITE_INTEGER: c_int = 1;
+    pub static SQLITE_FLOAT: c_int = 2;
+    pub static SQLITE_TEXT: c_int = 3;
+    pub static SQLITE_BLOB: c_int = 4;
+    pub static SQLITE_NULL: c_int = 5;
+
     // A function because Rust doesn't like casting from an int to a function
     // pointer in a static declaration
     pub fn SQLITE_TRANSIENT() -> extern "C" fn(*c_void) {
@@ -31,15 +47,72 @@ mod ffi {
         fn sqlite3_reset(pStmt: *sqlite3_stmt) -> c_int;
         fn sqlite3_bind_text(pStmt: *sqlite3_stmt, idx: c_int, text: *c_char,
                              n: c_int, free: extern "C" fn(*c_void)) -> c_int;
+        fn sqlite3_bind_int64(pStmt: *sqlite3_stmt, idx: c_int, val: i64) -> c_int;
+        fn sqlite3_bind_double(pStmt: *sqlite3_stmt, idx: c_int, val: c_double) -> c_int;
+        fn sqlite3_bind_blob(pStmt: *sqlite3_stmt, idx: c_int, data: *c_void,
+                             n: c_int, free: extern "C" fn(*c_void)) -> c_int;
+        fn sqlite3_bind_null(pStmt: *sqlite3_stmt, idx: c_int) -> c_int;
+        fn sqlite3_bind_parameter_index(pStmt: *sqlite3_stmt, zName: *c_char) -> c_int;
         fn sqlite3_step(pStmt: *sqlite3_stmt) -> c_int;
         fn sqlite3_column_count(pStmt: *sqlite3_stmt) -> c_int;
         fn sqlite3_column_text(pStmt: *sqlite3_stmt, iCol: c_int) -> *c_char;
+        fn sqlite3_column_int64(pStmt: *sqlite3_stmt, iCol: c_int) -> i64;
+        fn sqlite3_column_double(pStmt: *sqlite3_stmt, iCol: c_int) -> c_double;
+        fn sqlite3_column_blob(pStmt: *sqlite3_stmt, iCol: c_int) -> *c_void;
+        fn sqlite3_column_bytes(pStmt: *sqlite3_stmt, iCol: c_int) -> c_int;
+        fn sqlite3_column_type(pStmt: *sqlite3_stmt, iCol: c_int) -> c_int;
         fn sqlite3_finalize(pStmt: *sqlite3_stmt) -> c_int;
+        fn sqlite3_backup_init(pDest: *sqlite3, zDestName: *c_char,
+                               pSource: *sqlite3, zSourceName: *c_char)
+                               -> *sqlite3_backup;
+        fn sqlite3_backup_step(p: *sqlite3_backup, nPage: c_int) -> c_int;
+        fn sqlite3_backup_finish(p: *sqlite3_backup) -> c_int;
+        fn sqlite3_backup_remaining(p: *sqlite3_backup) -> c_int;
+        fn sqlite3_backup_pagecount(p: *sqlite3_backup) -> c_int;
+        fn sqlite3_sleep(ms: c_int) -> c_int;
+        fn sqlite3_create_function_v2(db: *sqlite3, zFunctionName: *c_char,
+                                      nArg: c_int, eTextRep: c_int,
+                                      pApp: *c_void,
+                                      xFunc: extern "C" fn(*sqlite3_context, c_int,
+                                                            *mut *sqlite3_value),
+                                      xStep: *c_void, xFinal: *c_void,
+                                      xDestroy: extern "C" fn(*c_void)) -> c_int;
+        fn sqlite3_user_data(ctx: *sqlite3_context) -> *c_void;
+        fn sqlite3_value_type(v: *sqlite3_value) -> c_int;
+        fn sqlite3_value_text(v: *sqlite3_value) -> *c_char;
+        fn sqlite3_value_int64(v: *sqlite3_value) -> i64;
+        fn sqlite3_value_double(v: *sqlite3_value) -> c_double;
+        fn sqlite3_value_blob(v: *sqlite3_value) -> *c_void;
+        fn sqlite3_value_bytes(v: *sqlite3_value) -> c_int;
+        fn sqlite3_result_text(ctx: *sqlite3_context, text: *c_char, n: c_int,
+                               free: extern "C" fn(*c_void));
+        fn sqlite3_result_int64(ctx: *sqlite3_context, val: i64);
+        fn sqlite3_result_double(ctx: *sqlite3_context, val: c_double);
+        fn sqlite3_result_blob(ctx: *sqlite3_context, data: *c_void, n: c_int,
+                               free: extern "C" fn(*c_void));
+        fn sqlite3_result_null(ctx: *sqlite3_context);
+        fn sqlite3_result_error(ctx: *sqlite3_context, text: *c_char, n: c_int);
+        fn sqlite3_trace(db: *sqlite3, xTrace: extern "C" fn(*c_void, *c_char),
+                         pArg: *c_void) -> *c_void;
+        fn sqlite3_profile(db: *sqlite3,
+                           xProfile: extern "C" fn(*c_void, *c_char, u64),
+                           pArg: *c_void) -> *c_void;
+    }
+
+    // Passing a NULL callback disables tracing/profiling; Rust doesn't
+    // allow casting 0 to a function pointer in a static, same as
+    // SQLITE_TRANSIENT above.
+    pub fn NO_TRACE() -> extern "C" fn(*c_void, *c_char) {
+        unsafe { cast::transmute(0) }
+    }
+
+    pub fn NO_PROFILE() -> extern "C" fn(*c_void, *c_char, u64) {
+        unsafe { cast::transmute(0) }
     }
 }
 
 pub fn open(filename: &str) -> Result<~Connection, ~str> {
-    let mut conn = ~Connection {conn: ptr::null()};
+    let mut conn = ~Connection {conn: ptr::null(), trace_cb: None, profile_cb: None};
     let ret = do filename.as_c_str |c_filename| {
         unsafe { ffi::sqlite3_open(c_filename, &mut conn.conn) }
     };
@@ -51,7 +124,9 @@ pub fn open(filename: &str) -> Result<~Connection, ~str> {
 }
 
 pub struct Connection {
-    priv conn: *ffi::sqlite3
+    priv conn: *ffi::sqlite3,
+    priv trace_cb: Option<~fn(&str)>,
+    priv profile_cb: Option<~fn(&str, u64)>
 }
 
 impl Drop for Connection {
@@ -113,6 +188,11 @@ impl Connection {
         self.prepare(query).chain(|stmt| stmt.update_params(params))
     }
 
+    pub fn update_named(&self, query: &str, params: &[(&str, @SqlType)])
+                        -> Result<uint, ~str> {
+        self.prepare(query).chain(|stmt| stmt.update_named(params))
+    }
+
     pub fn query<T>(&self, query: &str, blk: &fn (&mut ResultIterator) -> T)
                     -> Result<T, ~str> {
         self.query_params(query, [], blk)
@@ -126,19 +206,325 @@ impl Connection {
         Ok(blk(&mut it))
     }
 
-    pub fn in_transaction<T>(&self, blk: &fn(&Connection) -> Result<T, ~str>)
-                             -> Result<T, ~str> {
+    pub fn query_named<T>(&self, query: &str, params: &[(&str, @SqlType)],
+                          blk: &fn (&mut ResultIterator) -> T)
+                          -> Result<T, ~str> {
+        let stmt = ret_err!(self.prepare(query) { Ok(stmt) => stmt });
+        let mut it = ret_err!(stmt.query_named(params) { Ok(it) => it });
+        Ok(blk(&mut it))
+    }
+
+    /// Begin a transaction, returning a handle whose `commit`/`rollback`
+    /// report failures instead of swallowing them, and which rolls back
+    /// automatically if dropped without either being called.
+    pub fn transaction<'a>(&'a self) -> Result<Transaction<'a>, ~str> {
         ret_err!(self.update("BEGIN"));
+        Ok(Transaction {conn: self})
+    }
+
+    /// Copy this database's contents into `dst`, page by page, without
+    /// closing either connection. Equivalent to `backup_incremental` with
+    /// all pages copied in a single step.
+    pub fn backup_to(&self, dst: &Connection) -> Result<(), ~str> {
+        self.backup_incremental(dst, -1, |_, _| ())
+    }
+
+    /// Like `backup_to`, but copies `pages_per_step` pages at a time,
+    /// calling `progress(remaining, total)` after each step so callers can
+    /// report progress on a large backup.
+    pub fn backup_incremental(&self, dst: &Connection, pages_per_step: int,
+                              progress: &fn(uint, uint))
+                              -> Result<(), ~str> {
+        let backup = ret_err!(Backup::new(dst, self) { Ok(b) => b });
+
+        loop {
+            let done = ret_err!(backup.step(pages_per_step) { Ok(d) => d });
+            progress(backup.remaining(), backup.pagecount());
+
+            if done {
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub struct Transaction<'self> {
+    priv conn: &'self Connection
+}
+
+#[unsafe_destructor]
+impl<'self> Drop for Transaction<'self> {
+    fn drop(&self) {
+        // Not our problem if this fails -- we're already unwinding, the
+        // caller never called commit/rollback themselves, or the one they
+        // called failed and left the transaction open.
+        self.conn.update("ROLLBACK");
+    }
+}
+
+impl<'self> Transaction<'self> {
+    pub fn commit(self) -> Result<(), ~str> {
+        let ret = self.conn.update("COMMIT").chain(|_| Ok(()));
+        // Only skip the auto-rollback in Drop if COMMIT actually succeeded --
+        // otherwise the transaction is still open and must still be unwound.
+        if ret.is_ok() {
+            unsafe { cast::forget(self); }
+        }
+        ret
+    }
+
+    pub fn rollback(self) -> Result<(), ~str> {
+        let ret = self.conn.update("ROLLBACK").chain(|_| Ok(()));
+        if ret.is_ok() {
+            unsafe { cast::forget(self); }
+        }
+        ret
+    }
+
+    /// Open a nested SAVEPOINT, letting a partial rollback undo just the
+    /// work done since this call without aborting the whole transaction.
+    pub fn savepoint<'a>(&'a self, name: &str) -> Result<Savepoint<'a>, ~str> {
+        ret_err!(self.conn.update(fmt!("SAVEPOINT %s", name)));
+        Ok(Savepoint {conn: self.conn, name: name.to_owned()})
+    }
+}
+
+pub struct Savepoint<'self> {
+    priv conn: &'self Connection,
+    priv name: ~str
+}
+
+#[unsafe_destructor]
+impl<'self> Drop for Savepoint<'self> {
+    fn drop(&self) {
+        self.conn.update(fmt!("ROLLBACK TO %s", self.name));
+    }
+}
+
+impl<'self> Savepoint<'self> {
+    pub fn release(self) -> Result<(), ~str> {
+        let ret = self.conn.update(fmt!("RELEASE %s", self.name)).chain(|_| Ok(()));
+        if ret.is_ok() {
+            unsafe { cast::forget(self); }
+        }
+        ret
+    }
+
+    pub fn rollback(self) -> Result<(), ~str> {
+        let ret = self.conn.update(fmt!("ROLLBACK TO %s", self.name)).chain(|_| Ok(()));
+        if ret.is_ok() {
+            unsafe { cast::forget(self); }
+        }
+        ret
+    }
+}
+
+struct Backup<'self> {
+    priv dst: &'self Connection,
+    priv backup: *ffi::sqlite3_backup
+}
+
+#[unsafe_destructor]
+impl<'self> Drop for Backup<'self> {
+    fn drop(&self) {
+        unsafe { ffi::sqlite3_backup_finish(self.backup); }
+    }
+}
+
+impl<'self> Backup<'self> {
+    fn new<'a>(dst: &'a Connection, src: &'a Connection)
+              -> Result<Backup<'a>, ~str> {
+        let handle = do "main".as_c_str |c_dst_name| {
+            do "main".as_c_str |c_src_name| {
+                unsafe {
+                    ffi::sqlite3_backup_init(dst.conn, c_dst_name,
+                                             src.conn, c_src_name)
+                }
+            }
+        };
+
+        if ptr::is_null(handle) {
+            return Err(dst.get_error());
+        }
+
+        Ok(Backup {dst: dst, backup: handle})
+    }
+
+    fn step(&self, n_pages: int) -> Result<bool, ~str> {
+        match unsafe { ffi::sqlite3_backup_step(self.backup, n_pages as c_int) } {
+            ffi::SQLITE_DONE => Ok(true),
+            ffi::SQLITE_OK => Ok(false),
+            // Transient lock contention on src or dst -- SQLite's backup
+            // docs call this expected and recommend a short sleep before
+            // retrying rather than giving up.
+            ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => {
+                unsafe { ffi::sqlite3_sleep(15); }
+                Ok(false)
+            }
+            _ => Err(self.dst.get_error())
+        }
+    }
+
+    fn remaining(&self) -> uint {
+        unsafe { ffi::sqlite3_backup_remaining(self.backup) as uint }
+    }
+
+    fn pagecount(&self) -> uint {
+        unsafe { ffi::sqlite3_backup_pagecount(self.backup) as uint }
+    }
+}
+
+/// A SQLite value as seen by a user-defined scalar function, tagged the
+/// same way as `SqlValueType`.
+pub enum Value {
+    IntValue(int),
+    FloatValue(f64),
+    TextValue(~str),
+    BlobValue(~[u8]),
+    NullValue
+}
+
+fn value_from_raw(v: *ffi::sqlite3_value) -> Value {
+    match unsafe { ffi::sqlite3_value_type(v) } {
+        ffi::SQLITE_INTEGER =>
+            IntValue(unsafe { ffi::sqlite3_value_int64(v) } as int),
+        ffi::SQLITE_FLOAT =>
+            FloatValue(unsafe { ffi::sqlite3_value_double(v) }),
+        ffi::SQLITE_TEXT => {
+            let raw = unsafe { ffi::sqlite3_value_text(v) };
+            TextValue(unsafe { str::raw::from_c_str(raw) })
+        }
+        ffi::SQLITE_BLOB => {
+            let len = unsafe { ffi::sqlite3_value_bytes(v) };
+            let data = unsafe { ffi::sqlite3_value_blob(v) };
+            BlobValue(unsafe { vec::raw::from_buf_raw(data as *u8, len as uint) })
+        }
+        _ => NullValue
+    }
+}
+
+fn result_to_context(ctx: *ffi::sqlite3_context, result: Result<Value, ~str>) {
+    match result {
+        Ok(IntValue(i)) =>
+            unsafe { ffi::sqlite3_result_int64(ctx, i as i64) },
+        Ok(FloatValue(f)) =>
+            unsafe { ffi::sqlite3_result_double(ctx, f) },
+        Ok(TextValue(s)) => do s.as_c_str |c_str| {
+            unsafe {
+                ffi::sqlite3_result_text(ctx, c_str, -1, ffi::SQLITE_TRANSIENT())
+            }
+        },
+        Ok(BlobValue(b)) => do b.as_imm_buf |p, len| {
+            unsafe {
+                ffi::sqlite3_result_blob(ctx, p as *c_void,
+                                         len as c_int, ffi::SQLITE_TRANSIENT())
+            }
+        },
+        Ok(NullValue) => unsafe { ffi::sqlite3_result_null(ctx) },
+        Err(msg) => do msg.as_c_str |c_msg| {
+            unsafe { ffi::sqlite3_result_error(ctx, c_msg, -1) }
+        }
+    }
+}
+
+type ScalarFn = ~fn(&[Value]) -> Result<Value, ~str>;
+
+extern "C" fn scalar_function_trampoline(ctx: *ffi::sqlite3_context, argc: c_int,
+                                         argv: *mut *ffi::sqlite3_value) {
+    unsafe {
+        let user_data = ffi::sqlite3_user_data(ctx);
+        let f: &ScalarFn = cast::transmute(user_data);
+        let args: ~[Value] = vec::from_fn(argc as uint, |i| {
+            value_from_raw(*argv.offset(i as int))
+        });
+        result_to_context(ctx, (*f)(args));
+    }
+}
+
+extern "C" fn scalar_function_destroy(user_data: *c_void) {
+    unsafe {
+        let _: ~ScalarFn = cast::transmute(user_data);
+        // Dropped here, freeing the boxed closure.
+    }
+}
 
-        let ret = blk(self);
+impl Connection {
+    /// Register a Rust closure as a scalar SQL function callable as
+    /// `name(arg1, ..., argN)` from any query run on this connection.
+    pub fn create_scalar_function(&self, name: &str, n_args: int, f: ScalarFn)
+                                  -> Result<(), ~str> {
+        let boxed: ~ScalarFn = ~f;
+        let user_data: *c_void = unsafe { cast::transmute(boxed) };
+
+        let ret = do name.as_c_str |c_name| {
+            unsafe {
+                ffi::sqlite3_create_function_v2(self.conn, c_name,
+                                                n_args as c_int,
+                                                ffi::SQLITE_UTF8, user_data,
+                                                scalar_function_trampoline,
+                                                ptr::null(), ptr::null(),
+                                                scalar_function_destroy)
+            }
+        };
 
-        // TODO: What to do with errors here?
         match ret {
-            Ok(_) => self.update("COMMIT"),
-            Err(_) => self.update("ROLLBACK")
+            ffi::SQLITE_OK => Ok(()),
+            _ => Err(self.get_error())
+        }
+    }
+}
+
+extern "C" fn trace_trampoline(arg: *c_void, sql: *c_char) {
+    unsafe {
+        let conn: &Connection = cast::transmute(arg);
+        match conn.trace_cb {
+            Some(ref f) => (*f)(str::raw::from_c_str(sql)),
+            None => ()
+        }
+    }
+}
+
+extern "C" fn profile_trampoline(arg: *c_void, sql: *c_char, nanos: u64) {
+    unsafe {
+        let conn: &Connection = cast::transmute(arg);
+        match conn.profile_cb {
+            Some(ref f) => (*f)(str::raw::from_c_str(sql), nanos),
+            None => ()
+        }
+    }
+}
+
+impl Connection {
+    /// Receive the expanded SQL text of every statement this connection
+    /// executes. Pass `None` to stop tracing.
+    pub fn trace(&mut self, cb: Option<~fn(&str)>) {
+        self.trace_cb = cb;
+
+        match self.trace_cb {
+            Some(_) => unsafe {
+                ffi::sqlite3_trace(self.conn, trace_trampoline,
+                                   cast::transmute(&*self))
+            },
+            None => unsafe {
+                ffi::sqlite3_trace(self.conn, ffi::NO_TRACE(), ptr::null())
+            }
         };
+    }
 
-        ret
+    /// Receive the SQL text and wall-clock time (in nanoseconds) of every
+    /// statement this connection executes. Pass `None` to stop profiling.
+    pub fn profile(&mut self, cb: Option<~fn(&str, u64)>) {
+        self.profile_cb = cb;
+
+        match self.profile_cb {
+            Some(_) => unsafe {
+                ffi::sqlite3_profile(self.conn, profile_trampoline,
+                                     cast::transmute(&*self))
+            },
+            None => unsafe {
+                ffi::sqlite3_profile(self.conn, ffi::NO_PROFILE(), ptr::null())
+            }
+        };
     }
 }
 
@@ -161,16 +547,34 @@ impl<'self> PreparedStatement<'self> {
 
     fn bind_params(&self, params: &[@SqlType]) -> Result<(), ~str> {
         for params.iter().enumerate().advance |(idx, param)| {
-            let ret = do param.to_sql_str().as_c_str |c_param| {
-                unsafe {
-                    ffi::sqlite3_bind_text(self.stmt, (idx+1) as c_int,
-                                           c_param, -1,
-                                           ffi::SQLITE_TRANSIENT())
-                }
+            let ret = param.bind(self.stmt, (idx+1) as c_int);
+
+            if ret != ffi::SQLITE_OK {
+                return Err(fmt!("%s (binding a %s value at index %u)",
+                                self.conn.get_error(), param.sql_type().name(),
+                                idx + 1));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bind_named_params(&self, params: &[(&str, @SqlType)]) -> Result<(), ~str> {
+        for params.iter().advance |&(name, param)| {
+            let idx = do name.as_c_str |c_name| {
+                unsafe { ffi::sqlite3_bind_parameter_index(self.stmt, c_name) }
             };
 
+            if idx == 0 {
+                return Err(fmt!("no such named parameter: %s", name));
+            }
+
+            let ret = param.bind(self.stmt, idx);
+
             if ret != ffi::SQLITE_OK {
-                return Err(self.conn.get_error());
+                return Err(fmt!("%s (binding a %s value for :%s)",
+                                self.conn.get_error(), param.sql_type().name(),
+                                name));
             }
         }
 
@@ -187,7 +591,16 @@ impl<'self> PreparedStatement<'self> {
     pub fn update_params(&self, params: &[@SqlType]) -> Result<uint, ~str> {
         self.reset();
         ret_err!(self.bind_params(params));
+        self.do_step()
+    }
+
+    pub fn update_named(&self, params: &[(&str, @SqlType)]) -> Result<uint, ~str> {
+        self.reset();
+        ret_err!(self.bind_named_params(params));
+        self.do_step()
+    }
 
+    fn do_step(&self) -> Result<uint, ~str> {
         let ret = unsafe { ffi::sqlite3_step(self.stmt) };
 
         match ret {
@@ -208,6 +621,13 @@ impl<'self> PreparedStatement<'self> {
         ret_err!(self.bind_params(params));
         Ok(ResultIterator {stmt: self})
     }
+
+    pub fn query_named(&'self self, params: &[(&str, @SqlType)])
+            -> Result<ResultIterator<'self>, ~str> {
+        self.reset();
+        ret_err!(self.bind_named_params(params));
+        Ok(ResultIterator {stmt: self})
+    }
 }
 
 pub struct ResultIterator<'self> {
@@ -241,29 +661,166 @@ impl<'self> Container for Row<'self> {
 
 impl<'self> Row<'self> {
     pub fn get<T: SqlType>(&self, idx: uint) -> Option<T> {
-        let raw = unsafe {
-            ffi::sqlite3_column_text(self.stmt.stmt, idx as c_int)
-        };
+        SqlType::from_column(self.stmt.stmt, idx as c_int)
+    }
+}
 
-        if ptr::is_null(raw) {
-            return None;
-        }
+/// The native SQLite storage class a `SqlType` value is bound or read as.
+/// See https://www.sqlite.org/datatype3.html for SQLite's own rules on
+/// how these interact with column affinity.
+pub enum SqlValueType {
+    Integer,
+    Float,
+    Text,
+    Blob,
+    Null
+}
 
-        SqlType::from_sql_str(unsafe { str::raw::from_c_str(raw) })
+impl SqlValueType {
+    fn name(&self) -> &'static str {
+        match *self {
+            Integer => "integer",
+            Float => "float",
+            Text => "text",
+            Blob => "blob",
+            Null => "null"
+        }
     }
 }
 
 pub trait SqlType {
-    fn to_sql_str(&self) -> ~str;
-    fn from_sql_str(sql_str: &str) -> Option<Self>;
+    /// Which native SQLite type this value binds as.
+    fn sql_type(&self) -> SqlValueType;
+
+    /// Bind this value into the given statement at the given 1-based index,
+    /// returning the raw sqlite3_bind_* result code.
+    fn bind(&self, stmt: *ffi::sqlite3_stmt, idx: c_int) -> c_int;
+
+    /// Read this type out of the given column, returning `None` if the
+    /// column's actual storage class can't be coerced to `Self`. Numeric
+    /// widening (an integer-valued column read as `f64`) is coerced;
+    /// anything else (e.g. reading a text column as `int`) is not.
+    fn from_column(stmt: *ffi::sqlite3_stmt, idx: c_int) -> Option<Self>;
 }
 
 impl SqlType for int {
-    fn to_sql_str(&self) -> ~str {
-        self.to_str()
+    fn sql_type(&self) -> SqlValueType { Integer }
+
+    fn bind(&self, stmt: *ffi::sqlite3_stmt, idx: c_int) -> c_int {
+        unsafe { ffi::sqlite3_bind_int64(stmt, idx, *self as i64) }
     }
 
-    fn from_sql_str(sql_str: &str) -> Option<int> {
-        FromStr::from_str(sql_str)
+    fn from_column(stmt: *ffi::sqlite3_stmt, idx: c_int) -> Option<int> {
+        match unsafe { ffi::sqlite3_column_type(stmt, idx) } {
+            ffi::SQLITE_INTEGER =>
+                Some(unsafe { ffi::sqlite3_column_int64(stmt, idx) } as int),
+            _ => None
+        }
+    }
+}
+
+impl SqlType for f64 {
+    fn sql_type(&self) -> SqlValueType { Float }
+
+    fn bind(&self, stmt: *ffi::sqlite3_stmt, idx: c_int) -> c_int {
+        unsafe { ffi::sqlite3_bind_double(stmt, idx, *self) }
+    }
+
+    fn from_column(stmt: *ffi::sqlite3_stmt, idx: c_int) -> Option<f64> {
+        match unsafe { ffi::sqlite3_column_type(stmt, idx) } {
+            // sqlite3_column_double coerces integer-valued columns to a
+            // float for free, same as SQLite's own type affinity rules.
+            ffi::SQLITE_FLOAT | ffi::SQLITE_INTEGER =>
+                Some(unsafe { ffi::sqlite3_column_double(stmt, idx) }),
+            _ => None
+        }
+    }
+}
+
+impl SqlType for bool {
+    fn sql_type(&self) -> SqlValueType { Integer }
+
+    fn bind(&self, stmt: *ffi::sqlite3_stmt, idx: c_int) -> c_int {
+        unsafe { ffi::sqlite3_bind_int64(stmt, idx, if *self { 1 } else { 0 }) }
+    }
+
+    fn from_column(stmt: *ffi::sqlite3_stmt, idx: c_int) -> Option<bool> {
+        match unsafe { ffi::sqlite3_column_type(stmt, idx) } {
+            ffi::SQLITE_INTEGER =>
+                Some(unsafe { ffi::sqlite3_column_int64(stmt, idx) } != 0),
+            _ => None
+        }
+    }
+}
+
+impl SqlType for ~str {
+    fn sql_type(&self) -> SqlValueType { Text }
+
+    fn bind(&self, stmt: *ffi::sqlite3_stmt, idx: c_int) -> c_int {
+        do self.as_c_str |c_str| {
+            unsafe {
+                ffi::sqlite3_bind_text(stmt, idx, c_str, -1,
+                                       ffi::SQLITE_TRANSIENT())
+            }
+        }
+    }
+
+    fn from_column(stmt: *ffi::sqlite3_stmt, idx: c_int) -> Option<~str> {
+        match unsafe { ffi::sqlite3_column_type(stmt, idx) } {
+            ffi::SQLITE_TEXT => {
+                let raw = unsafe { ffi::sqlite3_column_text(stmt, idx) };
+                Some(unsafe { str::raw::from_c_str(raw) })
+            }
+            _ => None
+        }
+    }
+}
+
+impl SqlType for ~[u8] {
+    fn sql_type(&self) -> SqlValueType { Blob }
+
+    fn bind(&self, stmt: *ffi::sqlite3_stmt, idx: c_int) -> c_int {
+        do self.as_imm_buf |p, len| {
+            unsafe {
+                ffi::sqlite3_bind_blob(stmt, idx, p as *c_void,
+                                       len as c_int, ffi::SQLITE_TRANSIENT())
+            }
+        }
+    }
+
+    fn from_column(stmt: *ffi::sqlite3_stmt, idx: c_int) -> Option<~[u8]> {
+        match unsafe { ffi::sqlite3_column_type(stmt, idx) } {
+            ffi::SQLITE_BLOB => {
+                let len = unsafe { ffi::sqlite3_column_bytes(stmt, idx) };
+                let data = unsafe { ffi::sqlite3_column_blob(stmt, idx) };
+                Some(unsafe {
+                    vec::raw::from_buf_raw(data as *u8, len as uint)
+                })
+            }
+            _ => None
+        }
+    }
+}
+
+impl<T: SqlType> SqlType for Option<T> {
+    fn sql_type(&self) -> SqlValueType {
+        match *self {
+            Some(ref v) => v.sql_type(),
+            None => Null
+        }
+    }
+
+    fn bind(&self, stmt: *ffi::sqlite3_stmt, idx: c_int) -> c_int {
+        match *self {
+            Some(ref v) => v.bind(stmt, idx),
+            None => unsafe { ffi::sqlite3_bind_null(stmt, idx) }
+        }
+    }
+
+    fn from_column(stmt: *ffi::sqlite3_stmt, idx: c_int) -> Option<Option<T>> {
+        match unsafe { ffi::sqlite3_column_type(stmt, idx) } {
+            ffi::SQLITE_NULL => Some(None),
+            _ => SqlType::from_column(stmt, idx).map(Some)
+        }
     }
 }
\ No newline at end of file